@@ -1,51 +1,150 @@
 use rand::Rng;
 use serde_json::Value;
 use std::cmp::Ordering;
-use std::collections::VecDeque;
+use std::collections::BinaryHeap;
+use std::time::Duration;
 //Here we are storing the order as struct to matching scenarios and sorting the prices and time
 //we can have epoch time in place of timestamp which would give exact time.
+//One aggregated price level in the Market-By-Price view ,a (price, quantity) pair like the exchange's depth entries.
+type Level = (f64, f64);
+//A depth snapshot ,bids descending and asks ascending ,mirroring the /api/v3/depth payload shape.
+type DepthSnapshot = (Vec<Level>, Vec<Level>);
+
+//A limit order carries the price it is willing to rest at ,a market order just takes whatever the book offers.
+#[derive(Debug, Clone)]
+enum OrderType {
+    Limit { price: f64 },
+    Market,
+}
+
 #[derive(Debug, Clone)]
 struct Order {
     id: usize,
+    order_type: OrderType,
+    //the quantity still resting in the book ,always net of whatever has already traded ,so a cancel only ever pulls this remaining amount back out
+    quantity: f64,
+    timestamp: u64,
+}
+
+impl Order {
+    //The resting price of the order ,only limit orders ever rest in the book so markets never reach here.
+    fn price(&self) -> f64 {
+        match self.order_type {
+            OrderType::Limit { price } => price,
+            OrderType::Market => unreachable!("market orders never rest in the book"),
+        }
+    }
+}
+
+//A single executed trade ,tying the aggressing/resting pair back to the order-book event that produced it.
+//This is the microstructure record the cancellation and market-order logic reason about (how much of an order has traded).
+#[derive(Debug, Clone)]
+struct Trade {
+    buy_id: usize,
+    sell_id: usize,
     price: f64,
     quantity: f64,
     timestamp: u64,
 }
 
-//Here am using double ended queue to add and remove orders
-//why double ended queue? we can easily extract the higher priority order in less time as each and every we add the order we are going to sort.(similar to pq discussed in the interview)
+//Price-time priority wrappers so the raw orders can live in a BinaryHeap (which is a max-heap).
+//Buy: the highest price is the best order ,ties break toward the earlier (lower timestamp) order.
+//We express "earlier wins" by making the earlier timestamp compare as greater ,so it pops off the top first.
+#[derive(Debug, Clone)]
+struct Buy(Order);
+//Sell: the lowest price is the best order ,so we flip the price comparison to turn the max-heap into a min-by-price heap.
+#[derive(Debug, Clone)]
+struct Sell(Order);
+
+impl Ord for Buy {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.0.price().partial_cmp(&other.0.price()).unwrap() {
+            Ordering::Equal => other.0.timestamp.cmp(&self.0.timestamp),
+            ordering => ordering,
+        }
+    }
+}
+impl PartialOrd for Buy {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for Buy {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Buy {}
+
+impl Ord for Sell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match other.0.price().partial_cmp(&self.0.price()).unwrap() {
+            Ordering::Equal => other.0.timestamp.cmp(&self.0.timestamp),
+            ordering => ordering,
+        }
+    }
+}
+impl PartialOrd for Sell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for Sell {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Sell {}
+
+//The book keeps a max-heap of buys and a min-heap of sells ,so top-of-book is O(1) and inserts are O(log n)
+//instead of the old push-then-full-sort which was O(n log n) on every single add.
 #[derive(Debug)]
 struct OrderBook {
-    buy_orders: VecDeque<Order>,
-    sell_orders: VecDeque<Order>,
+    buy_orders: BinaryHeap<Buy>,
+    sell_orders: BinaryHeap<Sell>,
+    //Every executed trade is kept here so a caller can reconstruct an order's fills after the fact.
+    trades: Vec<Trade>,
     next_order_id: usize,
     current_price: f64,
+    //The market we track ,no longer hard-coded so other symbols can be mirrored.
+    symbol: String,
+    //Monotonic arrival counter used as the timestamp for orders ingested from the exchange depth feed ,keeping time priority stable across repeated syncs.
+    sync_seq: u64,
+    //Ids of the orders currently resting from the last depth snapshot ,so a re-sync can drop the stale levels before re-seeding instead of duplicating them.
+    synced_ids: Vec<usize>,
 }
 
 impl OrderBook {
-    //Initialisation of the order book ,creating 2 queues and initalizing the order id with 1
+    //Initialisation of the order book for the default BTCUSDT market.
     async fn new() -> Self {
-        let current_price = Self::fetch_current_price().await.unwrap_or(45700.0);
+        Self::with_symbol("BTCUSDT").await
+    }
+
+    //Initialisation for an arbitrary market ,creating 2 heaps and initalizing the order id with 1.
+    async fn with_symbol(symbol: &str) -> Self {
+        let current_price = Self::fetch_current_price(symbol).await.unwrap_or(45700.0);
         Self {
-            buy_orders: VecDeque::new(),
-            sell_orders: VecDeque::new(),
+            buy_orders: BinaryHeap::new(),
+            sell_orders: BinaryHeap::new(),
+            trades: Vec::new(),
             next_order_id: 1,
             current_price,
+            symbol: symbol.to_string(),
+            sync_seq: 0,
+            synced_ids: Vec::new(),
         }
     }
 
-    async fn fetch_current_price() -> Option<f64> {
+    async fn fetch_current_price(symbol: &str) -> Option<f64> {
         let url = "https://api.binance.com/api/v3/ticker/price";
         if let Ok(response) = reqwest::get(url).await {
             if let Ok(result) = response.text().await {
                 if let Ok(json_data) = serde_json::from_str::<Value>(&result) {
                     if let Some(array) = json_data.as_array() {
                         for obj in array {
-                            if let Some(symbol) = obj.get("symbol") {
-                                if symbol == "BTCUSDT" {
-                                    if let Some(price) = obj.get("price") {
-                                        return price.as_str().and_then(|p| p.parse::<f64>().ok());
-                                    }
+                            if obj.get("symbol").map(|s| s == symbol).unwrap_or(false) {
+                                if let Some(price) = obj.get("price") {
+                                    return price.as_str().and_then(|p| p.parse::<f64>().ok());
                                 }
                             }
                         }
@@ -55,79 +154,446 @@ impl OrderBook {
         }
         None
     }
-    //Creating the struct based on the price ,quantity,time stamp
-    fn add_order(&mut self, price: f64, quantity: f64, is_buy: bool, timestamp: u64) {
+
+    //Parse one `["price","qty"]` level from the depth response into floats ,the exchange sends both as strings.
+    fn parse_level(level: &Value) -> Option<(f64, f64)> {
+        let pair = level.as_array()?;
+        let price = pair.first()?.as_str()?.parse::<f64>().ok()?;
+        let quantity = pair.get(1)?.as_str()?.parse::<f64>().ok()?;
+        Some((price, quantity))
+    }
+
+    //Drop the orders left over from the previous depth snapshot ,so a fresh full snapshot replaces them instead of stacking on top.
+    fn drop_synced_levels(&mut self) {
+        if self.synced_ids.is_empty() {
+            return;
+        }
+        let stale = std::mem::take(&mut self.synced_ids);
+        let buys: Vec<Buy> = self
+            .buy_orders
+            .drain()
+            .filter(|b| !stale.contains(&b.0.id))
+            .collect();
+        self.buy_orders = buys.into_iter().collect();
+        let sells: Vec<Sell> = self
+            .sell_orders
+            .drain()
+            .filter(|s| !stale.contains(&s.0.id))
+            .collect();
+        self.sell_orders = sells.into_iter().collect();
+    }
+
+    //Seed/refresh the book from the exchange's /api/v3/depth snapshot ,turning each bid/ask level into a resting order so
+    //match_orders runs against real market depth instead of only random bulk orders. `limit` mirrors the endpoint's limit param.
+    //The endpoint returns a *full* snapshot each call ,so we first drop the levels from the previous sync and then re-seed ,
+    //otherwise repeated calls would duplicate every level and inflate the quantities N-fold.
+    async fn sync_from_depth(&mut self, limit: usize) {
+        let url = format!(
+            "https://api.binance.com/api/v3/depth?symbol={}&limit={}",
+            self.symbol, limit
+        );
+        let Ok(response) = reqwest::get(&url).await else {
+            return;
+        };
+        let Ok(result) = response.text().await else {
+            return;
+        };
+        let Ok(json_data) = serde_json::from_str::<Value>(&result) else {
+            return;
+        };
+
+        self.drop_synced_levels();
+
+        if let Some(bids) = json_data.get("bids").and_then(|b| b.as_array()) {
+            for level in bids {
+                if let Some((price, quantity)) = Self::parse_level(level) {
+                    let id = self.next_order_id;
+                    let timestamp = self.sync_seq;
+                    self.sync_seq += 1;
+                    self.add_order(OrderType::Limit { price }, quantity, true, timestamp);
+                    self.synced_ids.push(id);
+                }
+            }
+        }
+        if let Some(asks) = json_data.get("asks").and_then(|a| a.as_array()) {
+            for level in asks {
+                if let Some((price, quantity)) = Self::parse_level(level) {
+                    let id = self.next_order_id;
+                    let timestamp = self.sync_seq;
+                    self.sync_seq += 1;
+                    self.add_order(OrderType::Limit { price }, quantity, false, timestamp);
+                    self.synced_ids.push(id);
+                }
+            }
+        }
+    }
+
+    //Keep the book synchronized by re-pulling the depth snapshot every `interval` ,for `iterations` rounds
+    //(a finite bound so callers can replay/mirror a window of live exchange state without blocking forever).
+    async fn keep_synced(&mut self, limit: usize, interval: Duration, iterations: usize) {
+        for _ in 0..iterations {
+            self.sync_from_depth(limit).await;
+            tokio::time::sleep(interval).await;
+        }
+    }
+    //Creating the struct based on the order type ,quantity,time stamp and dropping it into the matching heap.
+    //Price-time priority is now maintained by the heap ordering itself ,so there is no per-insert sort anymore.
+    //A market order never rests (there is no price to rest at) ,so it walks the opposite side immediately and we hand the
+    //caller back whatever quantity could not be filled. A limit order rests and so always reports a zero remainder.
+    fn add_order(&mut self, order_type: OrderType, quantity: f64, is_buy: bool, timestamp: u64) -> f64 {
+        let id = self.next_order_id;
+        self.next_order_id += 1;
+
+        if let OrderType::Market = order_type {
+            return self.execute_market(id, quantity, is_buy, timestamp);
+        }
+
         let order = Order {
-            id: self.next_order_id,
-            price,
+            id,
+            order_type,
             quantity,
             timestamp,
         };
-        self.next_order_id += 1;
-        //Here we will be adding the orders into the queue and sorting will happen based on the prices and if prices are equal then we will sort with time
-        //logic::Buy order prices are sorted in descending order and sell order prices are sorted in ascending order which makes orders to fill faster
         if is_buy {
-            self.buy_orders.push_back(order);
-            self.buy_orders.make_contiguous().sort_by(|a, b| {
-                match b.price.partial_cmp(&a.price).unwrap() {
-                    Ordering::Equal => a.timestamp.cmp(&b.timestamp),
-                    other => other,
-                }
-            });
+            self.buy_orders.push(Buy(order));
         } else {
-            self.sell_orders.push_back(order);
-            self.sell_orders.make_contiguous().sort_by(|a, b| {
-                match a.price.partial_cmp(&b.price).unwrap() {
-                    Ordering::Equal => a.timestamp.cmp(&b.timestamp),
-                    other => other,
-                }
-            });
+            self.sell_orders.push(Sell(order));
         }
         //Each and every time you added a order we have to check the order book and match if any
         self.match_orders();
+        0.0
+    }
+
+    //Execute a market order against the best available prices ,walking the opposite side until the requested quantity is
+    //filled or that side is exhausted. There is no crossing condition to check ,a market order takes whatever is there.
+    //Returns the remainder that could not be filled (0.0 when fully filled).
+    fn execute_market(&mut self, id: usize, mut quantity: f64, is_buy: bool, timestamp: u64) -> f64 {
+        while quantity > 0.0 {
+            if is_buy {
+                let Some(mut resting) = self.sell_orders.pop().map(|s| s.0) else {
+                    break;
+                };
+                let transaction_quantity = quantity.min(resting.quantity);
+                println!(
+                    "Matched: Market Buy Order {} and Sell Order {} at price {} for quantity {}",
+                    id, resting.id, resting.price(), transaction_quantity
+                );
+                self.trades.push(Trade {
+                    buy_id: id,
+                    sell_id: resting.id,
+                    price: resting.price(),
+                    quantity: transaction_quantity,
+                    timestamp,
+                });
+                quantity -= transaction_quantity;
+                resting.quantity -= transaction_quantity;
+                if resting.quantity > 0.0 {
+                    self.sell_orders.push(Sell(resting));
+                }
+            } else {
+                let Some(mut resting) = self.buy_orders.pop().map(|b| b.0) else {
+                    break;
+                };
+                let transaction_quantity = quantity.min(resting.quantity);
+                println!(
+                    "Matched: Buy Order {} and Market Sell Order {} at price {} for quantity {}",
+                    resting.id, id, resting.price(), transaction_quantity
+                );
+                self.trades.push(Trade {
+                    buy_id: resting.id,
+                    sell_id: id,
+                    price: resting.price(),
+                    quantity: transaction_quantity,
+                    timestamp,
+                });
+                quantity -= transaction_quantity;
+                resting.quantity -= transaction_quantity;
+                if resting.quantity > 0.0 {
+                    self.buy_orders.push(Buy(resting));
+                }
+            }
+        }
+        quantity
     }
 
     //This is to modify the order ,we can further increase the functionality to change price of the order based upon the quantity and price
+    //Only the quantity changes here which does not affect the price-time ordering ,so we just rebuild the heap after the edit.
     fn modify_order(&mut self, order_id: usize, new_quantity: f64) {
-        for order in self
+        let mut buys: Vec<Buy> = self.buy_orders.drain().collect();
+        if let Some(order) = buys.iter_mut().find(|b| b.0.id == order_id) {
+            order.0.quantity = new_quantity;
+            self.buy_orders = buys.into_iter().collect();
+            return;
+        }
+        self.buy_orders = buys.into_iter().collect();
+
+        let mut sells: Vec<Sell> = self.sell_orders.drain().collect();
+        if let Some(order) = sells.iter_mut().find(|s| s.0.id == order_id) {
+            order.0.quantity = new_quantity;
+        }
+        self.sell_orders = sells.into_iter().collect();
+    }
+
+    //Cancel a resting order by id ,we do a positional search over whichever side holds it and drop the entry.
+    //The already-filled part of an order is gone (it became a real trade) ,so we only ever pull the remaining unfilled quantity out of the book.
+    //Returns false if no live order with that id is resting ,mirroring the remove-by-id success reporting.
+    fn cancel_order(&mut self, order_id: usize) -> bool {
+        let mut buys: Vec<Buy> = self.buy_orders.drain().collect();
+        if let Some(pos) = buys.iter().position(|b| b.0.id == order_id) {
+            buys.remove(pos);
+            self.buy_orders = buys.into_iter().collect();
+            return true;
+        }
+        self.buy_orders = buys.into_iter().collect();
+
+        let mut sells: Vec<Sell> = self.sell_orders.drain().collect();
+        if let Some(pos) = sells.iter().position(|s| s.0.id == order_id) {
+            sells.remove(pos);
+            self.sell_orders = sells.into_iter().collect();
+            return true;
+        }
+        self.sell_orders = sells.into_iter().collect();
+        false
+    }
+
+    //Partial cancel of the residual ,pulling `quantity` off whichever side holds the order while leaving the rest resting.
+    //If `quantity` meets or exceeds the remaining size this degenerates into a full cancel (the entry is dropped).
+    //Returns false if no live order with that id is resting.
+    fn cancel_partial(&mut self, order_id: usize, quantity: f64) -> bool {
+        let mut buys: Vec<Buy> = self.buy_orders.drain().collect();
+        if let Some(pos) = buys.iter().position(|b| b.0.id == order_id) {
+            if quantity >= buys[pos].0.quantity {
+                buys.remove(pos);
+            } else {
+                buys[pos].0.quantity -= quantity;
+            }
+            self.buy_orders = buys.into_iter().collect();
+            return true;
+        }
+        self.buy_orders = buys.into_iter().collect();
+
+        let mut sells: Vec<Sell> = self.sell_orders.drain().collect();
+        if let Some(pos) = sells.iter().position(|s| s.0.id == order_id) {
+            if quantity >= sells[pos].0.quantity {
+                sells.remove(pos);
+            } else {
+                sells[pos].0.quantity -= quantity;
+            }
+            self.sell_orders = sells.into_iter().collect();
+            return true;
+        }
+        self.sell_orders = sells.into_iter().collect();
+        false
+    }
+    //Collapse the resting orders on one side into distinct price levels with summed quantity ,the Market-By-Price view
+    //that Binance's /api/v3/depth returns. `descending` sorts bids high-to-low and asks low-to-high (best level first).
+    fn aggregate_levels(orders: impl Iterator<Item = Level>, descending: bool) -> Vec<Level> {
+        let mut levels: Vec<Level> = Vec::new();
+        for (price, quantity) in orders {
+            if let Some(level) = levels.iter_mut().find(|(p, _)| *p == price) {
+                level.1 += quantity;
+            } else {
+                levels.push((price, quantity));
+            }
+        }
+        levels.sort_by(|a, b| {
+            if descending {
+                b.0.partial_cmp(&a.0).unwrap()
+            } else {
+                a.0.partial_cmp(&b.0).unwrap()
+            }
+        });
+        levels
+    }
+
+    //Aggregated depth snapshot limited to `depth` price levels per side ,comparable to the exchange's /api/v3/depth payload
+    //(which defaults to 100 levels). Bids come back descending ,asks ascending.
+    fn depth_snapshot(&self, depth: usize) -> DepthSnapshot {
+        let bids = Self::aggregate_levels(
+            self.buy_orders.iter().map(|b| (b.0.price(), b.0.quantity)),
+            true,
+        );
+        let asks = Self::aggregate_levels(
+            self.sell_orders.iter().map(|s| (s.0.price(), s.0.quantity)),
+            false,
+        );
+        (
+            bids.into_iter().take(depth).collect(),
+            asks.into_iter().take(depth).collect(),
+        )
+    }
+
+    //Top of book as (best_bid_price, best_bid_qty, best_ask_price, best_ask_qty, spread) ,where the quantities are the
+    //aggregated size resting at the best price. Returns None when either side is empty (no two-sided quote).
+    fn best_quote(&self) -> Option<(f64, f64, f64, f64, f64)> {
+        let (bids, asks) = self.depth_snapshot(1);
+        let (bid_price, bid_qty) = bids.first().copied()?;
+        let (ask_price, ask_qty) = asks.first().copied()?;
+        Some((bid_price, bid_qty, ask_price, ask_qty, ask_price - bid_price))
+    }
+
+    //Every recorded trade this order took part in ,on either the buy or the sell side.
+    fn trades_for(&self, order_id: usize) -> Vec<&Trade> {
+        self.trades
+            .iter()
+            .filter(|t| t.buy_id == order_id || t.sell_id == order_id)
+            .collect()
+    }
+
+    //Total quantity this order has had filled across all of its trades.
+    fn filled_quantity_for(&self, order_id: usize) -> f64 {
+        self.trades_for(order_id).iter().map(|t| t.quantity).sum()
+    }
+
+    //Volume-weighted average execution price for this order ,None if it never traded.
+    fn vwap_for(&self, order_id: usize) -> Option<f64> {
+        let trades = self.trades_for(order_id);
+        let quantity: f64 = trades.iter().map(|t| t.quantity).sum();
+        if quantity <= 0.0 {
+            return None;
+        }
+        let notional: f64 = trades.iter().map(|t| t.price * t.quantity).sum();
+        Some(notional / quantity)
+    }
+
+    //Batch-auction matching ,as used by batch-auction DEX designs ,clearing every resting order at one uniform price.
+    //We scan the distinct limit prices in the book ,and for each candidate p compute demand (buys willing to pay >= p)
+    //and supply (sells willing to accept <= p); the executable volume at p is min(demand, supply). The clearing price is
+    //the one that maximises that volume ,breaking ties toward the price nearest current_price. We then fill the eligible
+    //orders at that single price in price-time priority ,applying the partial fill to the marginal order. Returns None if
+    //nothing crosses.
+    fn run_batch_auction(&mut self) -> Option<(f64, f64)> {
+        let mut prices: Vec<f64> = self
             .buy_orders
-            .iter_mut()
-            .chain(self.sell_orders.iter_mut())
-        {
-            if order.id == order_id {
-                order.quantity = new_quantity;
-                break;
+            .iter()
+            .map(|b| b.0.price())
+            .chain(self.sell_orders.iter().map(|s| s.0.price()))
+            .collect();
+        prices.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        prices.dedup();
+
+        let mut best: Option<(f64, f64)> = None;
+        for &p in &prices {
+            let demand: f64 = self
+                .buy_orders
+                .iter()
+                .filter(|b| b.0.price() >= p)
+                .map(|b| b.0.quantity)
+                .sum();
+            let supply: f64 = self
+                .sell_orders
+                .iter()
+                .filter(|s| s.0.price() <= p)
+                .map(|s| s.0.quantity)
+                .sum();
+            let volume = demand.min(supply);
+            let closer_to_mid = |candidate: f64, incumbent: f64| {
+                (candidate - self.current_price).abs() < (incumbent - self.current_price).abs()
+            };
+            match best {
+                Some((bp, bv)) if volume < bv || (volume == bv && !closer_to_mid(p, bp)) => {}
+                _ => best = Some((p, volume)),
+            }
+        }
+
+        let (clearing_price, volume) = best?;
+        if volume <= 0.0 {
+            return None;
+        }
+
+        //Fill the eligible buys (willing to pay >= clearing) in price-time priority ,the heap already hands them back best-first.
+        let mut remaining = volume;
+        while remaining > 0.0 {
+            match self.buy_orders.peek() {
+                Some(buy) if buy.0.price() >= clearing_price => {}
+                _ => break,
+            }
+            let mut order = self.buy_orders.pop().unwrap().0;
+            let fill = remaining.min(order.quantity);
+            println!(
+                "Batch fill: Buy Order {} at clearing price {} for quantity {}",
+                order.id, clearing_price, fill
+            );
+            //Uniform-price clearing has no single counterparty per fill ,so we log one Trade per filled order with the
+            //opposite side's id left as 0 (no counterparty). This keeps trades_for/filled_quantity_for/vwap_for accurate
+            //for each order without inventing a bogus pairing.
+            self.trades.push(Trade {
+                buy_id: order.id,
+                sell_id: 0,
+                price: clearing_price,
+                quantity: fill,
+                timestamp: order.timestamp,
+            });
+            order.quantity -= fill;
+            remaining -= fill;
+            if order.quantity > 0.0 {
+                self.buy_orders.push(Buy(order));
             }
         }
+
+        //Symmetrically fill the eligible sells (willing to accept <= clearing).
+        let mut remaining = volume;
+        while remaining > 0.0 {
+            match self.sell_orders.peek() {
+                Some(sell) if sell.0.price() <= clearing_price => {}
+                _ => break,
+            }
+            let mut order = self.sell_orders.pop().unwrap().0;
+            let fill = remaining.min(order.quantity);
+            println!(
+                "Batch fill: Sell Order {} at clearing price {} for quantity {}",
+                order.id, clearing_price, fill
+            );
+            self.trades.push(Trade {
+                buy_id: 0,
+                sell_id: order.id,
+                price: clearing_price,
+                quantity: fill,
+                timestamp: order.timestamp,
+            });
+            order.quantity -= fill;
+            remaining -= fill;
+            if order.quantity > 0.0 {
+                self.sell_orders.push(Sell(order));
+            }
+        }
+
+        Some((clearing_price, volume))
     }
+
     //here we will be matching the best buy order for best sell order if and only if the buy order price is greater than sell order price,
-    //Logic for matching and partial  matching is done and we can also increase or decrease current price based upon this order matchings
+    //We peek the two heap tops (O(1)) ,cross while best_buy.price >= best_sell.price ,and reinsert whichever side is only partially filled.
     //min(buyorder.quantity,sellorder.quantity) gives the exact amount of order that can match
     fn match_orders(&mut self) {
-        while let (Some(mut buy_order), Some(mut sell_order)) = (
-            self.buy_orders.front().cloned(),
-            self.sell_orders.front().cloned(),
-        ) {
-            if buy_order.price >= sell_order.price {
-                let transaction_quantity = buy_order.quantity.min(sell_order.quantity);
-                println!(
-                    "Matched: Buy Order {} and Sell Order {} at price {} for quantity {}",
-                    buy_order.id, sell_order.id, sell_order.price, transaction_quantity
-                );
+        while let (Some(buy), Some(sell)) = (self.buy_orders.peek(), self.sell_orders.peek()) {
+            if buy.0.price() < sell.0.price() {
+                break;
+            }
 
-                if buy_order.quantity > transaction_quantity {
-                    self.buy_orders.front_mut().unwrap().quantity -= transaction_quantity;
-                } else {
-                    self.buy_orders.pop_front();
-                }
+            let mut buy_order = self.buy_orders.pop().unwrap().0;
+            let mut sell_order = self.sell_orders.pop().unwrap().0;
+            let transaction_quantity = buy_order.quantity.min(sell_order.quantity);
+            println!(
+                "Matched: Buy Order {} and Sell Order {} at price {} for quantity {}",
+                buy_order.id, sell_order.id, sell_order.price(), transaction_quantity
+            );
 
-                if sell_order.quantity > transaction_quantity {
-                    self.sell_orders.front_mut().unwrap().quantity -= transaction_quantity;
-                } else {
-                    self.sell_orders.pop_front();
-                }
-            } else {
-                break;
+            self.trades.push(Trade {
+                buy_id: buy_order.id,
+                sell_id: sell_order.id,
+                price: sell_order.price(),
+                quantity: transaction_quantity,
+                timestamp: buy_order.timestamp.max(sell_order.timestamp),
+            });
+
+            buy_order.quantity -= transaction_quantity;
+            sell_order.quantity -= transaction_quantity;
+
+            if buy_order.quantity > 0.0 {
+                self.buy_orders.push(Buy(buy_order));
+            }
+            if sell_order.quantity > 0.0 {
+                self.sell_orders.push(Sell(sell_order));
             }
         }
     }
@@ -137,30 +603,34 @@ impl OrderBook {
 fn create_bulk_orders(order_book: &mut OrderBook, num_orders: usize) {
     let mut rng = rand::thread_rng();
     let base_price = 45700.0;
-    for i in 0..num_orders {
+    for _ in 0..num_orders {
         let price = base_price + rng.gen_range(-100..100) as f64;
         let quantity = rng.gen_range(0.1..5.0);
         let is_buy = rng.gen_bool(0.5);
-        order_book.add_order(price, quantity, is_buy, i as u64);
+        //Draw the timestamp from the book's single monotonic clock so bulk orders never collide with the depth-seeded ones
+        //and the earlier-wins tie-break stays deterministic.
+        let timestamp = order_book.sync_seq;
+        order_book.sync_seq += 1;
+        order_book.add_order(OrderType::Limit { price }, quantity, is_buy, timestamp);
     }
 }
 #[tokio::main]
 async fn main() {
     let mut order_book = OrderBook::new().await;
 
-    // order_book.add_order(101.0, 5.0, true, 1);
-    // order_book.add_order(100.5, 3.0, true, 2);
-    // order_book.add_order(100.0, 4.0, false, 3);
-    // order_book.add_order(99.5, 2.0, false, 4);
-
-    // order_book.add_order(45700.0, 1.0, true, 1);
-    // order_book.add_order(45650.0, 2.5, true, 2);
-    // order_book.add_order(45600.0, 1.0, true, 1);
-    // order_book.add_order(45710.0, 1.0, false, 2);
-    // order_book.add_order(45720.0, 1.5, false, 3);
-    // order_book.add_order(45750.0, 2.0, false, 4);
+    //Seed the book from the live exchange depth so matching runs against real market state ,then add a few local orders on top.
+    order_book.sync_from_depth(100).await;
     create_bulk_orders(&mut order_book, 10);
-    println!("Remaining Buy Orders: {:?}", order_book.buy_orders);
-    println!("Remaining Sell Orders: {:?}", order_book.sell_orders);
+
+    //Market-By-Price snapshot after the batch ,so the book state reads like the live exchange depth endpoint.
+    let (bids, asks) = order_book.depth_snapshot(100);
+    println!("Bids (price, qty): {:?}", bids);
+    println!("Asks (price, qty): {:?}", asks);
+    if let Some((bid_price, bid_qty, ask_price, ask_qty, spread)) = order_book.best_quote() {
+        println!(
+            "Best bid {} x {} | Best ask {} x {} | Spread {}",
+            bid_price, bid_qty, ask_price, ask_qty, spread
+        );
+    }
     println!("Current BTC/USDT Price: {}", order_book.current_price);
 }